@@ -4,7 +4,7 @@ use std::path::Path;
 
 use vivotk::render::wgpu::{
     builder::RenderBuilder, camera::Camera, controls::Controller, metrics_reader::MetricsReader,
-    render_manager::AdaptiveManager, renderer::Renderer,
+    playback::PlaybackMode, render_manager::AdaptiveManager, renderer::Renderer,
 };
 
 /// Plays a folder of pcd files in lexicographical order
@@ -90,6 +90,7 @@ fn main() {
         (args.width, args.height),
         metrics,
         args.bg_color.to_str().unwrap(),
+        PlaybackMode::Loop,
     ));
 
     if args.show_controls {
@@ -332,6 +332,7 @@ fn main() {
                                     PCMetadata {
                                         frame_offset: req.frame_offset,
                                         object_id: req.object_id,
+                                        is_reversed: req.is_reversed,
                                     },
                                     output_rx,
                                 )));
@@ -358,6 +359,7 @@ fn main() {
         buffer_capacity,
         total_frames,
         segment_size,
+        args.playback_mode,
         shutdown_recv,
     );
     let viewport_predictor: Box<dyn ViewportPrediction> = match args.viewport_prediction_type {
@@ -414,7 +416,8 @@ fn main() {
             camera,
             (args.width, args.height),
             metrics,
-            args.bg_color.to_str().unwrap()
+            args.bg_color.to_str().unwrap(),
+            args.playback_mode,
         ));
     // };
     if args.show_controls {
@@ -14,6 +14,43 @@ pub struct Bounds {
     pub max_z: f32,
 }
 
+/// Explicit cut coordinates along each axis, used to partition bounds into unequal cells.
+///
+/// An empty axis falls back to the uniform partitioning of that axis.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SplitPlanes {
+    pub x: Vec<f32>,
+    pub y: Vec<f32>,
+    pub z: Vec<f32>,
+}
+
+impl SplitPlanes {
+    pub fn new(x: Vec<f32>, y: Vec<f32>, z: Vec<f32>) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.x.is_empty() && self.y.is_empty() && self.z.is_empty()
+    }
+
+    /// Number of cells along each axis produced by `Bounds::partition`,
+    /// using the uniform `partitions` for the axes without cut coordinates
+    pub fn partitions(&self, partitions: (usize, usize, usize)) -> (usize, usize, usize) {
+        let count = |planes: &Vec<f32>, n: usize| {
+            if planes.is_empty() {
+                n
+            } else {
+                planes.len() + 1
+            }
+        };
+        (
+            count(&self.x, partitions.0),
+            count(&self.y, partitions.1),
+            count(&self.z, partitions.2),
+        )
+    }
+}
+
 impl Bounds {
     fn new(min_x: f32, max_x: f32, min_y: f32, max_y: f32, min_z: f32, max_z: f32) -> Self {
         Self {
@@ -80,24 +117,31 @@ impl Bounds {
         ]
     }
 
-    pub fn partition(&self, partitions: (usize, usize, usize)) -> Vec<Bounds> {
-        let x_step = (self.max_x - self.min_x + DELTA) / partitions.0 as f32;
-        let y_step = (self.max_y - self.min_y + DELTA) / partitions.1 as f32;
-        let z_step = (self.max_z - self.min_z + DELTA) / partitions.2 as f32;
+    /// Partitions the bounds into cells, ordered by x, then y, then z.
+    ///
+    /// Each axis is split uniformly into the number of partitions given for that axis,
+    /// unless `split_planes` provides explicit cut coordinates for it, in which case that count is not used.
+    /// See `SplitPlanes::partitions` for the resulting number of cells along each axis.
+    pub fn partition(
+        &self,
+        partitions: (usize, usize, usize),
+        split_planes: Option<&SplitPlanes>,
+    ) -> Vec<Bounds> {
+        let no_planes = vec![];
+        let (x_planes, y_planes, z_planes) = match split_planes {
+            Some(planes) => (&planes.x, &planes.y, &planes.z),
+            None => (&no_planes, &no_planes, &no_planes),
+        };
+        let x_edges = axis_edges(self.min_x, self.max_x, partitions.0, x_planes);
+        let y_edges = axis_edges(self.min_y, self.max_y, partitions.1, y_planes);
+        let z_edges = axis_edges(self.min_z, self.max_z, partitions.2, z_planes);
 
         let mut bounds = vec![];
 
-        for z in 0..partitions.2 {
-            for y in 0..partitions.1 {
-                for x in 0..partitions.0 {
-                    bounds.push(Bounds::new(
-                        self.min_x + x as f32 * x_step,
-                        self.min_x + (x + 1) as f32 * x_step,
-                        self.min_y + y as f32 * y_step,
-                        self.min_y + (y + 1) as f32 * y_step,
-                        self.min_z + z as f32 * z_step,
-                        self.min_z + (z + 1) as f32 * z_step,
-                    ));
+        for z in z_edges.windows(2) {
+            for y in y_edges.windows(2) {
+                for x in x_edges.windows(2) {
+                    bounds.push(Bounds::new(x[0], x[1], y[0], y[1], z[0], z[1]));
                 }
             }
         }
@@ -127,3 +171,65 @@ impl Bounds {
             && point.z <= self.max_z
     }
 }
+
+/// Returns the cell edges along one axis, from `min` to `max + DELTA`.
+/// The cut coordinates are sorted and clamped into the range so that the number of cells stays fixed.
+fn axis_edges(min: f32, max: f32, partitions: usize, planes: &[f32]) -> Vec<f32> {
+    let end = max + DELTA;
+    if planes.is_empty() {
+        let step = (end - min) / partitions as f32;
+        return (0..=partitions).map(|i| min + i as f32 * step).collect();
+    }
+
+    let mut cuts = planes.iter().map(|p| p.clamp(min, end)).collect::<Vec<_>>();
+    cuts.sort_by(f32::total_cmp);
+
+    let mut edges = Vec::with_capacity(cuts.len() + 2);
+    edges.push(min);
+    edges.extend(cuts);
+    edges.push(end);
+    edges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit_bounds() -> Bounds {
+        Bounds::new(0.0, 1.0, 0.0, 1.0, 0.0, 1.0)
+    }
+
+    #[test]
+    fn test_partition_uniform() {
+        let bounds = unit_bounds().partition((2, 3, 4), None);
+        assert_eq!(bounds.len(), 24);
+        assert_eq!(bounds[0].min_x, 0.0);
+        assert_eq!(bounds[0].max_x, bounds[1].min_x);
+    }
+
+    #[test]
+    fn test_partition_unsorted_planes() {
+        let split_planes = SplitPlanes::new(vec![0.75, 0.25, 0.5], vec![], vec![]);
+        let bounds = unit_bounds().partition((1, 1, 1), Some(&split_planes));
+        assert_eq!(bounds.len(), 4);
+        let min_x = bounds.iter().map(|b| b.min_x).collect::<Vec<_>>();
+        assert_eq!(min_x, vec![0.0, 0.25, 0.5, 0.75]);
+    }
+
+    #[test]
+    fn test_partition_duplicated_and_out_of_range_planes() {
+        // every plane adds a cell, even if it is empty
+        let split_planes = SplitPlanes::new(vec![0.5, 0.5], vec![-1.0, 2.0], vec![]);
+        let bounds = unit_bounds().partition((2, 2, 2), Some(&split_planes));
+        assert_eq!(
+            split_planes.partitions((2, 2, 2)),
+            (3, 3, 2),
+            "axes with planes should have planes.len() + 1 cells"
+        );
+        assert_eq!(bounds.len(), 3 * 3 * 2);
+        for b in &bounds {
+            assert!(b.min_x <= b.max_x && b.min_y <= b.max_y && b.min_z <= b.max_z);
+            assert!(b.min_y >= 0.0 && b.max_y <= 1.0 + DELTA);
+        }
+    }
+}
@@ -1,13 +1,17 @@
 use serde::{Deserialize, Serialize};
 
-use super::bounds::Bounds;
+use super::bounds::{Bounds, SplitPlanes};
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct MetaData {
     pub bounds: Vec<Bounds>,
     pub base_point_num: Vec<Vec<usize>>,
     pub additional_point_num: Vec<Vec<usize>>,
+    /// Number of uniform partitions along each axis. Axes with split planes are partitioned by them instead.
     pub partitions: (usize, usize, usize),
+    /// Explicit cut coordinates for non-uniform partitioning. Partitions are uniform when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub split_planes: Option<SplitPlanes>,
 }
 
 impl MetaData {
@@ -16,12 +20,14 @@ impl MetaData {
         base_point_num: Vec<Vec<usize>>,
         additional_point_num: Vec<Vec<usize>>,
         partitions: (usize, usize, usize),
+        split_planes: Option<SplitPlanes>,
     ) -> Self {
         Self {
             bounds,
             base_point_num,
             additional_point_num,
             partitions,
+            split_planes,
         }
     }
 
@@ -31,6 +37,7 @@ impl MetaData {
             base_point_num: vec![],
             additional_point_num: vec![],
             partitions: (0, 0, 0),
+            split_planes: None,
         }
     }
 
@@ -44,4 +51,62 @@ impl MetaData {
         self.base_point_num.push(base_point_num);
         self.additional_point_num.push(additional_point_num);
     }
+
+    /// Number of segments each point cloud is partitioned into
+    pub fn num_segments(&self) -> usize {
+        let (x, y, z) = match &self.split_planes {
+            Some(split_planes) => split_planes.partitions(self.partitions),
+            None => self.partitions,
+        };
+        x * y * z
+    }
+
+    /// Partitions the given bounds into the cells of each segment
+    pub fn partition(&self, bound: &Bounds) -> Vec<Bounds> {
+        bound.partition(self.partitions, self.split_planes.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bound() -> Bounds {
+        Bounds {
+            min_x: 0.0,
+            max_x: 1.0,
+            min_y: 0.0,
+            max_y: 1.0,
+            min_z: 0.0,
+            max_z: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_num_segments_matches_partition() {
+        let uniform = MetaData::new(vec![], vec![], vec![], (2, 3, 2), None);
+        assert_eq!(uniform.num_segments(), uniform.partition(&bound()).len());
+
+        let split_planes = SplitPlanes::new(vec![0.1, 0.2, 0.3], vec![], vec![0.5]);
+        let non_uniform = MetaData::new(vec![], vec![], vec![], (2, 3, 2), Some(split_planes));
+        assert_eq!(non_uniform.num_segments(), 4 * 3 * 2);
+        assert_eq!(
+            non_uniform.num_segments(),
+            non_uniform.partition(&bound()).len()
+        );
+    }
+
+    #[test]
+    fn test_deserialize_without_split_planes() {
+        let json = r#"{
+            "bounds": [{"min_x": 0.0, "max_x": 1.0, "min_y": 0.0, "max_y": 1.0, "min_z": 0.0, "max_z": 1.0}],
+            "base_point_num": [[1, 2]],
+            "additional_point_num": [[3, 4]],
+            "partitions": [1, 1, 2]
+        }"#;
+        let metadata: MetaData = serde_json::from_str(json).unwrap();
+        assert!(metadata.split_planes.is_none());
+        assert_eq!(metadata.num_segments(), 2);
+        assert_eq!(metadata.partition(&metadata.bounds[0]).len(), 2);
+    }
 }
@@ -42,6 +42,7 @@ pub enum BufMsg {
 pub struct PCMetadata {
     pub object_id: u8,
     pub frame_offset: u64,
+    pub is_reversed: bool,
 }
 
 #[cfg(feature = "render")]
@@ -52,6 +53,7 @@ impl From<PCMetadata> for FrameRequest {
             frame_offset: val.frame_offset,
             // TODO: fix this once PCMetadata is updated
             camera_pos: None,
+            is_reversed: val.is_reversed,
         }
     }
 }
@@ -1,13 +1,14 @@
 use std::collections::VecDeque;
 use std::iter::zip;
 
-use crate::formats::bounds::Bounds;
+use crate::formats::bounds::{Bounds, SplitPlanes};
 use crate::formats::{pointxyzrgba::PointXyzRgba, PointCloud};
 use crate::utils::get_pc_bound;
 
 pub fn lodify(
     points: &PointCloud<PointXyzRgba>,
     partitions: (usize, usize, usize),
+    split_planes: Option<&SplitPlanes>,
     base_proportion: usize,
     points_per_voxel_threshold: usize,
 ) -> (
@@ -24,8 +25,8 @@ pub fn lodify(
 
         let (base_pc, additional_pc) = sample(points, base_point_num, points_per_voxel_threshold);
 
-        let partitioned_base_pc = partition(&base_pc, partitions);
-        let partitioned_add_pc = partition(&additional_pc, partitions);
+        let partitioned_base_pc = partition(&base_pc, partitions, split_planes);
+        let partitioned_add_pc = partition(&additional_pc, partitions, split_planes);
 
         let add_segments = partitioned_add_pc.segments.as_ref().unwrap();
         let pc_by_segment = (0..add_segments.len())
@@ -135,9 +136,10 @@ fn get_points_in_small_enough_voxel(
 fn partition(
     pc: &PointCloud<PointXyzRgba>,
     partitions: (usize, usize, usize),
+    split_planes: Option<&SplitPlanes>,
 ) -> PointCloud<PointXyzRgba> {
     let pc_bound = get_pc_bound(&pc);
-    let child_bounds = pc_bound.partition(partitions);
+    let child_bounds = pc_bound.partition(partitions, split_planes);
 
     let num_segments = child_bounds.len();
     let mut partitioned_points = vec![vec![]; num_segments];
@@ -212,7 +214,7 @@ mod test {
 
         let pc = PointCloud::new(4, points);
 
-        let result = partition(&pc, (2, 2, 2));
+        let result = partition(&pc, (2, 2, 2), None);
         let segments = result.segments.unwrap();
 
         assert_eq!(result.points.len(), 4);
@@ -226,4 +228,40 @@ mod test {
         assert_eq!(segments[6].point_indices.len(), 0);
         assert_eq!(segments[7].point_indices.len(), 2);
     }
+
+    #[test]
+    fn test_partition_with_split_planes() {
+        let points = (0..4)
+            .map(|i| PointXyzRgba {
+                x: i as f32,
+                y: i as f32,
+                z: i as f32,
+                r: 0,
+                g: 0,
+                b: 0,
+                a: 0,
+            })
+            .collect::<Vec<_>>();
+
+        let pc = PointCloud::new(4, points);
+
+        // x is split uniformly, y is split at the given planes
+        let split_planes = SplitPlanes::new(vec![], vec![0.5, 2.5], vec![]);
+        assert_eq!(split_planes.partitions((2, 2, 1)), (2, 3, 1));
+
+        let result = partition(&pc, (2, 2, 1), Some(&split_planes));
+        let segments = result.segments.unwrap();
+
+        assert_eq!(result.points.len(), 4);
+        assert_eq!(segments.len(), 6);
+        assert_eq!(segments[0].point_indices.len(), 1);
+        assert_eq!(segments[1].point_indices.len(), 0);
+        assert_eq!(segments[2].point_indices.len(), 1);
+        assert_eq!(segments[3].point_indices.len(), 1);
+        assert_eq!(segments[4].point_indices.len(), 0);
+        assert_eq!(segments[5].point_indices.len(), 1);
+        assert_eq!(segments[2].bounds.min_y, 0.5);
+        assert_eq!(segments[2].bounds.max_y, 2.5);
+        assert_eq!(result.points[segments[5].point_indices[0]].y, 3.0);
+    }
 }
@@ -7,7 +7,9 @@ use crossbeam_channel::Receiver;
 
 use crate::{
     formats::{
-        bounds::Bounds, pointxyzrgba::PointXyzRgba, pointxyzrgbanormal::PointXyzRgbaNormal,
+        bounds::{Bounds, SplitPlanes},
+        pointxyzrgba::PointXyzRgba,
+        pointxyzrgbanormal::PointXyzRgbaNormal,
         PointCloud,
     },
     metrics::Metrics,
@@ -49,7 +51,13 @@ pub enum PipelineMessage {
     IndexedPointCloudNormal(PointCloud<PointXyzRgbaNormal>, u32),
     IndexedPointCloudWithName(PointCloud<PointXyzRgba>, u32, String, bool),
     // PointCloud(PointCloud<PointXyzRgba>),
-    MetaData(Bounds, Vec<usize>, Vec<usize>, (usize, usize, usize)),
+    MetaData(
+        Bounds,
+        Vec<usize>,
+        Vec<usize>,
+        (usize, usize, usize),
+        Option<SplitPlanes>,
+    ),
     Metrics(Metrics),
     End,
     DummyForIncrement,
@@ -1,6 +1,7 @@
 use clap::Parser;
 
 use crate::{
+    formats::bounds::SplitPlanes,
     lodify::lodify::lodify,
     pipeline::{channel::Channel, PipelineMessage},
     utils::get_pc_bound,
@@ -31,10 +32,29 @@ pub struct Args {
         default_value = "10"
     )]
     points_per_voxel_threshold: usize,
+    /// Comma separated x coordinates to split at. Overrides the uniform x partitions when given.
+    #[clap(long, value_delimiter = ',', allow_hyphen_values = true, value_parser = parse_finite)]
+    x_split: Vec<f32>,
+    /// Comma separated y coordinates to split at. Overrides the uniform y partitions when given.
+    #[clap(long, value_delimiter = ',', allow_hyphen_values = true, value_parser = parse_finite)]
+    y_split: Vec<f32>,
+    /// Comma separated z coordinates to split at. Overrides the uniform z partitions when given.
+    #[clap(long, value_delimiter = ',', allow_hyphen_values = true, value_parser = parse_finite)]
+    z_split: Vec<f32>,
+}
+
+/// Parses a split plane coordinate, rejecting NaN and infinity
+fn parse_finite(s: &str) -> Result<f32, String> {
+    match s.parse::<f32>() {
+        Ok(value) if value.is_finite() => Ok(value),
+        Ok(_) => Err(format!("{s} is not a finite number")),
+        Err(e) => Err(e.to_string()),
+    }
 }
 
 pub struct Lodifier {
     partitions: (usize, usize, usize),
+    split_planes: Option<SplitPlanes>,
     base_proportion: usize,
     points_per_voxel_threshold: usize,
 }
@@ -42,8 +62,10 @@ pub struct Lodifier {
 impl Lodifier {
     pub fn from_args(args: Vec<String>) -> Box<dyn Subcommand> {
         let args: Args = Args::parse_from(args);
+        let split_planes = SplitPlanes::new(args.x_split, args.y_split, args.z_split);
         Box::new(Lodifier {
             partitions: (args.x_partition, args.y_partition, args.z_partition),
+            split_planes: (!split_planes.is_empty()).then_some(split_planes),
             base_proportion: args.base_proportion,
             points_per_voxel_threshold: args.points_per_voxel_threshold,
         })
@@ -58,6 +80,7 @@ impl Subcommand for Lodifier {
                     let (base_pc, pc_by_segment, base_point_nums, additional_point_nums) = lodify(
                         &pc,
                         self.partitions,
+                        self.split_planes.as_ref(),
                         self.base_proportion,
                         self.points_per_voxel_threshold,
                     );
@@ -85,12 +108,13 @@ impl Subcommand for Lodifier {
                         base_point_nums,
                         additional_point_nums,
                         self.partitions,
+                        self.split_planes.clone(),
                     ));
                 }
                 PipelineMessage::Metrics(_)
                 | PipelineMessage::IndexedPointCloudWithName(_, _, _, _)
                 | PipelineMessage::IndexedPointCloudNormal(_, _)
-                | PipelineMessage::MetaData(_, _, _, _)
+                | PipelineMessage::MetaData(_, _, _, _, _)
                 | PipelineMessage::DummyForIncrement => {}
                 PipelineMessage::End => {
                     channel.send(message);
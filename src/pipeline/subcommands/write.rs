@@ -211,6 +211,7 @@ impl Subcommand for Write {
                     base_point_num,
                     additional_point_num,
                     partitions,
+                    split_planes,
                 ) => {
                     if self.metadata.is_none() {
                         self.metadata = Some(MetaData::default());
@@ -221,6 +222,7 @@ impl Subcommand for Write {
                         additional_point_num.clone(),
                     );
                     self.metadata.as_mut().unwrap().partitions = *partitions;
+                    self.metadata.as_mut().unwrap().split_planes = split_planes.clone();
                 }
                 PipelineMessage::End => {
                     if let Some(metadata) = &self.metadata {
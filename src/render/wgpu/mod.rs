@@ -4,6 +4,7 @@ pub mod camera;
 pub mod controls;
 mod gpu;
 pub mod metrics_reader;
+pub mod playback;
 pub mod png;
 pub mod reader;
 pub mod render_manager;
@@ -0,0 +1,105 @@
+/**
+ * This file contains the playback mode shared by the renderer and the players' buffer managers
+ */
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlaybackMode {
+    /// Wrap around to the first frame after the last frame
+    Loop,
+    /// Reverse the playback direction at either end
+    PingPong,
+    /// Stop at the last frame
+    Once,
+}
+
+impl PlaybackMode {
+    /// Returns the offset of the frame played after `offset`, together with the new direction.
+    /// Returns None once playback has ended, i.e. the last frame has been reached in `PlaybackMode::Once`.
+    pub fn next_offset(
+        &self,
+        offset: u64,
+        total_frames: u64,
+        is_reversed: bool,
+    ) -> Option<(u64, bool)> {
+        let has_next = offset + 1 < total_frames;
+        let has_prev = offset > 0;
+        match self {
+            PlaybackMode::Loop => Some(((offset + 1) % total_frames, false)),
+            PlaybackMode::Once => has_next.then_some((offset + 1, false)),
+            PlaybackMode::PingPong => {
+                // turn around at either end, staying put if there is only a single frame
+                let is_reversed = if is_reversed {
+                    has_prev || !has_next
+                } else {
+                    !has_next && has_prev
+                };
+                if is_reversed && has_prev {
+                    Some((offset - 1, true))
+                } else if !is_reversed && has_next {
+                    Some((offset + 1, false))
+                } else {
+                    Some((offset, is_reversed))
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn offsets(mode: PlaybackMode, total_frames: u64, n: usize) -> Vec<Option<(u64, bool)>> {
+        let mut current = (0, false);
+        (0..n)
+            .map(|_| {
+                let next = mode.next_offset(current.0, total_frames, current.1);
+                if let Some(next) = next {
+                    current = next;
+                }
+                next
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_next_offset_loop() {
+        assert_eq!(
+            offsets(PlaybackMode::Loop, 3, 4),
+            vec![
+                Some((1, false)),
+                Some((2, false)),
+                Some((0, false)),
+                Some((1, false))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_next_offset_ping_pong() {
+        // no frame is repeated at the turnarounds
+        assert_eq!(
+            offsets(PlaybackMode::PingPong, 3, 6),
+            vec![
+                Some((1, false)),
+                Some((2, false)),
+                Some((1, true)),
+                Some((0, true)),
+                Some((1, false)),
+                Some((2, false))
+            ]
+        );
+        assert_eq!(
+            offsets(PlaybackMode::PingPong, 1, 2),
+            vec![Some((0, false)), Some((0, false))]
+        );
+    }
+
+    #[test]
+    fn test_next_offset_once() {
+        assert_eq!(
+            offsets(PlaybackMode::Once, 3, 3),
+            vec![Some((1, false)), Some((2, false)), None]
+        );
+    }
+}
@@ -21,6 +21,8 @@ pub trait RenderReader<T: Renderable> {
     fn len(&self) -> usize;
     fn is_empty(&self) -> bool;
     fn set_len(&mut self, len: usize);
+    /// Sets the direction in which the following frames are requested
+    fn set_reversed(&mut self, _is_reversed: bool) {}
 }
 //RenderReaderCameraPos for the one with CameraPosition
 pub trait RenderReaderCameraPos<T: Renderable> {
@@ -295,6 +297,7 @@ pub struct PcdAsyncReader {
     rx: Receiver<(FrameRequest, PointCloud<PointXyzRgba>)>,
     cache: Vec<(u64, PointCloud<PointXyzRgba>)>,
     tx: UnboundedSender<BufMsg>,
+    is_reversed: bool,
 }
 
 #[cfg(feature = "dash")]
@@ -308,11 +311,15 @@ pub struct FrameRequest {
     pub frame_offset: u64,
     /// The camera position when the frame was requested.
     pub camera_pos: Option<CameraPosition>,
+    /// Whether the frame is requested while playing backwards.
+    pub is_reversed: bool,
 }
 
 impl PartialEq for FrameRequest {
     fn eq(&self, other: &Self) -> bool {
-        self.object_id == other.object_id && self.frame_offset == other.frame_offset
+        self.object_id == other.object_id
+            && self.frame_offset == other.frame_offset
+            && self.is_reversed == other.is_reversed
     }
 }
 
@@ -330,6 +337,7 @@ impl PcdAsyncReader {
             // cache: HashMap::with_capacity(buffer_size as usize),
             cache: vec![],
             total_frames: 30, // default number of frames. Use `set_len` to overwrite this value
+            is_reversed: false,
         }
     }
 }
@@ -360,6 +368,7 @@ impl RenderReaderCameraPos<PointCloud<PointXyzRgba>> for PcdAsyncReader {
             object_id: 0,
             frame_offset: index % self.total_frames,
             camera_pos,
+            is_reversed: self.is_reversed,
         }));
         if let Ok((frame_req, pc)) = self.rx.recv() {
             if self.cache.len() >= 10 {
@@ -412,6 +421,7 @@ impl RenderReader<PointCloud<PointXyzRgba>> for PcdAsyncReader {
             object_id: 0,
             frame_offset: index % self.total_frames,
             camera_pos: None,
+            is_reversed: self.is_reversed,
         }));
         // Wait for the point cloud to be ready, cache it then return
         if let Ok((_frame_req, pc)) = self.rx.recv() {
@@ -437,6 +447,14 @@ impl RenderReader<PointCloud<PointXyzRgba>> for PcdAsyncReader {
     fn set_len(&mut self, len: usize) {
         self.total_frames = len as u64;
     }
+
+    fn set_reversed(&mut self, is_reversed: bool) {
+        // the cached frames were played in the other direction, so the buffer manager has to serve them again
+        if self.is_reversed != is_reversed {
+            self.cache.clear();
+        }
+        self.is_reversed = is_reversed;
+    }
 }
 
 // !! BufRenderReader is not used and comments are deleted.
@@ -18,6 +18,8 @@ pub trait RenderManager<T: Renderable> {
     fn set_len(&mut self, len: usize);
     fn set_camera_state(&mut self, camera_state: Option<CameraState>);
     fn should_redraw(&mut self, camera_state: &CameraState) -> bool;
+    /// Sets the direction in which the following frames are requested
+    fn set_reversed(&mut self, _is_reversed: bool) {}
 }
 
 pub struct AdaptiveManager {
@@ -97,10 +99,9 @@ impl AdaptiveManager {
                 exit(1);
             };
 
-            let add_paths =
-                (0..metadata.partitions.0 * metadata.partitions.1 * metadata.partitions.2)
-                    .map(|i| format!("{}/{}", src, i))
-                    .collect::<Vec<_>>();
+            let add_paths = (0..metadata.num_segments())
+                .map(|i| format!("{}/{}", src, i))
+                .collect::<Vec<_>>();
 
             let add_dirs = add_paths.iter().map(|s| Path::new(s)).collect::<Vec<_>>();
 
@@ -163,7 +164,7 @@ impl AdaptiveManager {
 
         if self.camera_state.is_none() || self.resolution_controller.is_none() {
             let mut pc = self.reader.get_at(index).unwrap();
-            pc.self_segment(base_point_num, &bound.partition(metadata.partitions));
+            pc.self_segment(base_point_num, &metadata.partition(&bound));
             return Some(pc);
         }
 
@@ -192,11 +193,7 @@ impl AdaptiveManager {
         let mut bound_indices = (0..base_point_num.len()).collect::<Vec<_>>();
         bound_indices.extend((0..to_load.len()).collect::<Vec<_>>());
 
-        pc.self_segment_with_bound_indices(
-            &offsets,
-            &bound_indices,
-            &bound.partition(metadata.partitions),
-        );
+        pc.self_segment_with_bound_indices(&offsets, &bound_indices, &metadata.partition(&bound));
 
         Some(pc)
     }
@@ -314,4 +311,8 @@ where
     fn should_redraw(&mut self, _camera_state: &CameraState) -> bool {
         false
     }
+
+    fn set_reversed(&mut self, is_reversed: bool) {
+        self.reader.set_reversed(is_reversed);
+    }
 }
@@ -3,6 +3,7 @@ use crate::render::wgpu::builder::{
 };
 use crate::render::wgpu::camera::{Camera, CameraState, CameraUniform};
 use crate::render::wgpu::gpu::WindowGpu;
+use crate::render::wgpu::playback::PlaybackMode;
 use crate::render::wgpu::render_manager::RenderManager;
 use log::debug;
 use std::iter;
@@ -70,6 +71,7 @@ where
     metrics_reader: Option<MetricsReader>,
     _data: PhantomData<U>,
     bg_color: Rgb,
+    playback_mode: PlaybackMode,
 }
 
 impl<T, U> Renderer<T, U>
@@ -84,6 +86,7 @@ where
         (width, height): (u32, u32),
         metrics_reader: Option<MetricsReader>,
         bg_color_str: &str,
+        playback_mode: PlaybackMode,
     ) -> Self {
         Self {
             reader,
@@ -93,6 +96,7 @@ where
             metrics_reader,
             _data: PhantomData::default(),
             bg_color: parse_bg_color(bg_color_str).unwrap(),
+            playback_mode,
         }
     }
 }
@@ -124,6 +128,7 @@ where
             self.camera_state,
             self.metrics_reader,
             self.bg_color,
+            self.playback_mode,
         );
         (state, window)
     }
@@ -147,6 +152,8 @@ where
 
     // Playback
     current_position: usize,
+    playback_mode: PlaybackMode,
+    is_reversed: bool,
     fps: f32, // the average playout fps
     time_to_advance: std::time::Duration,
     state: PlaybackState,
@@ -205,7 +212,7 @@ where
                 event_type,
             }) if *window_id == window.id() => match event_type {
                 EventType::Toggle => self.toggle(),
-                EventType::MoveTo(position) => self.move_to(*position),
+                EventType::MoveTo(position) => self.seek(*position),
                 _ => {}
             },
             _ => {}
@@ -232,6 +239,7 @@ where
         camera_state: CameraState,
         metrics_reader: Option<MetricsReader>,
         bg_color: Rgb,
+        playback_mode: PlaybackMode,
     ) -> Self {
         let initial_render = reader
             .start()
@@ -257,6 +265,8 @@ where
             camera_state,
 
             current_position: 0,
+            playback_mode,
+            is_reversed: false,
             fps,
             time_to_advance: std::time::Duration::from_secs(1).div_f32(fps),
             state: PlaybackState::Paused,
@@ -319,17 +329,44 @@ where
             0.9 * self.fps + 0.1 * (1.0 / time_taken.max(self.time_to_advance).as_secs_f32());
     }
 
+    /// Moves to the given position, playing forwards from there
+    fn seek(&mut self, position: usize) {
+        self.set_reversed(false);
+        self.move_to(position);
+    }
+
+    fn set_reversed(&mut self, is_reversed: bool) {
+        self.is_reversed = is_reversed;
+        self.reader.set_reversed(is_reversed);
+    }
+
     fn back(&mut self) {
         if self.current_position > 0 {
-            self.move_to(self.current_position - 1);
+            self.seek(self.current_position - 1);
         }
     }
 
     fn advance(&mut self) {
         if self.current_position == self.reader.len() - 1 {
-            self.move_to(0);
+            self.seek(0);
         } else {
-            self.move_to(self.current_position + 1);
+            self.seek(self.current_position + 1);
+        }
+    }
+
+    /// Moves to the next frame of the playback according to the playback mode
+    fn play_next(&mut self) {
+        match self.playback_mode.next_offset(
+            self.current_position as u64,
+            self.reader.len() as u64,
+            self.is_reversed,
+        ) {
+            Some((position, is_reversed)) => {
+                self.set_reversed(is_reversed);
+                self.move_to(position as usize);
+            }
+            // playback has ended, stop requesting frames
+            None => self.pause(),
         }
     }
 
@@ -368,7 +405,7 @@ where
         if self.state == PlaybackState::Play {
             self.time_since_last_update += dt;
             if self.time_since_last_update >= self.time_to_advance {
-                self.advance();
+                self.play_next();
                 self.time_since_last_update -= self.time_to_advance;
             }
         } else if self.reader.should_redraw(&self.camera_state) {
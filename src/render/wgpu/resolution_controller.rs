@@ -34,11 +34,7 @@ impl ResolutionController {
         let metadata = self.metadata.as_ref().unwrap();
 
         // let centroids = metadata.centroids.get(index).unwrap();
-        let bounds = metadata
-            .bounds
-            .get(index)
-            .unwrap()
-            .partition(metadata.partitions);
+        let bounds = metadata.partition(metadata.bounds.get(index).unwrap());
         let base_point_num = metadata.base_point_num.get(index).unwrap();
 
         let mut desired_num_points = vec![0; bounds.len()];
@@ -4,6 +4,7 @@ use std::path::PathBuf;
 
 use crate::vvplay_async_prefetch::enums::AbrType;
 use crate::vvplay_async_prefetch::enums::DecoderType;
+use crate::vvplay_async_prefetch::enums::PlaybackMode;
 use crate::vvplay_async_prefetch::enums::ThroughputPredictionType;
 use crate::vvplay_async_prefetch::enums::ViewportPredictionType;
 /**
@@ -61,6 +62,9 @@ pub struct Args {
     /// Alpha for throughput prediction. Only used for EMA, GAEMA, and LPEMA
     #[clap(long, default_value_t = 0.1)]
     pub throughput_alpha: f64,
+    /// Playback behaviour once the last frame is reached
+    #[clap(long = "playback", value_enum, default_value_t = PlaybackMode::Loop)]
+    pub playback_mode: PlaybackMode,
     #[clap(long = "vp", value_enum, default_value_t = ViewportPredictionType::Last)]
     pub viewport_prediction_type: ViewportPredictionType,
     /// Path to network trace for repeatable simulation. Network trace is expected to be given in Kbps
@@ -1,11 +1,14 @@
-use crate::dash::buffer::{Buffer, FrameStatus};
+use crate::dash::buffer::{Buffer, FrameStatus, RequestStatus};
 use crate::dash::ViewportPrediction;
 use crate::formats::pointxyzrgba::PointXyzRgba;
 use crate::formats::PointCloud;
 use crate::render::wgpu::{camera::CameraPosition, reader::FrameRequest};
 use crate::vvplay_async_prefetch::camera_trace::CameraTrace;
+use crate::vvplay_async_prefetch::enums::PlaybackMode;
 use crate::vvplay_async_prefetch::fetch_request::FetchRequest;
 use crate::BufMsg;
+use std::ops::Range;
+use tokio::sync::mpsc::UnboundedReceiver;
 
 /**
  * This file contains Buffer Manager struct and related implementation
@@ -36,10 +39,14 @@ pub struct BufferManager {
     buffer: Buffer,
     total_frames: usize,
     segment_size: u64,
+    /// playback_mode decides what happens once the last frame is reached.
+    /// The direction of playback is carried by each FrameRequest.
+    playback_mode: PlaybackMode,
     shutdown_recv: tokio::sync::watch::Receiver<bool>,
 }
 
 impl BufferManager {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         to_buf_rx: tokio::sync::mpsc::UnboundedReceiver<BufMsg>,
         buf_in_sx: tokio::sync::mpsc::UnboundedSender<FetchRequest>,
@@ -47,6 +54,7 @@ impl BufferManager {
         buffer_size: u64,
         total_frames: usize,
         segment_size: (u64, u64),
+        playback_mode: PlaybackMode,
         shutdown_recv: tokio::sync::watch::Receiver<bool>,
     ) -> Self {
         BufferManager {
@@ -56,21 +64,111 @@ impl BufferManager {
             frame_to_answer: None,
             total_frames,
             segment_size: segment_size.0,
+            playback_mode,
             shutdown_recv,
             // buffer size is given in seconds. however our frames are only segment_size.0 / segment_size.1 seconds long.
             buffer: Buffer::new(buffer_size as usize),
         }
     }
 
-    /// Get next frame request assuming playback is continuous
-    pub fn get_next_frame_req(&self, req: &FrameRequest) -> FrameRequest {
-        FrameRequest {
+    /// Get the request for the segment played after the segment containing `req`, according to the playback mode.
+    /// Returns None if there is no next segment to play, i.e. the last frame has been reached in `PlaybackMode::Once`.
+    pub fn get_next_frame_req(&self, req: &FrameRequest) -> Option<FrameRequest> {
+        let start = self.segment_start(req.frame_offset);
+        // a segment played backwards ends at its first frame
+        let last_frame = if req.is_reversed {
+            start
+        } else {
+            start + self.frames_in_segment(start) - 1
+        };
+        let (frame_offset, is_reversed) = self.playback_mode.next_offset(
+            last_frame,
+            self.total_frames as u64,
+            req.is_reversed,
+        )?;
+        Some(FrameRequest {
             object_id: req.object_id,
-            frame_offset: (req.frame_offset + self.segment_size) % self.total_frames as u64,
+            frame_offset: self.segment_start(frame_offset),
             camera_pos: req.camera_pos,
+            is_reversed,
+        })
+    }
+
+    /// Segments are always fetched from their first frame, whichever direction they are played in.
+    fn segment_start(&self, frame_offset: u64) -> u64 {
+        frame_offset - frame_offset % self.segment_size
+    }
+
+    /// Returns the number of frames in the segment starting at `segment_start`, as the last segment may be shorter
+    fn frames_in_segment(&self, segment_start: u64) -> u64 {
+        self.segment_size
+            .min((self.total_frames as u64).saturating_sub(segment_start))
+    }
+
+    /// Returns the offset of the frame that is played after `req` within the same segment
+    fn next_frame_in_segment(req: &FrameRequest) -> u64 {
+        if req.is_reversed {
+            req.frame_offset.saturating_sub(1)
+        } else {
+            req.frame_offset + 1
+        }
+    }
+
+    /// Returns the frames that can still be answered by a buffer entry
+    fn frames_in(&self, status: &RequestStatus) -> Range<u64> {
+        let offset = status.req.frame_offset;
+        match status.state {
+            // ready entries are keyed by the next frame to be played
+            FrameStatus::Ready(remaining, _) if status.req.is_reversed => {
+                offset + 1 - remaining as u64..offset + 1
+            }
+            FrameStatus::Ready(remaining, _) => offset..offset + remaining as u64,
+            FrameStatus::Fetching | FrameStatus::Decoding => {
+                offset..offset + self.frames_in_segment(offset)
+            }
         }
     }
 
+    /// Check whether the frame requested by the renderer can be answered by the entry at the front of the buffer
+    fn is_front_requested(&self, renderer_req: &FrameRequest) -> bool {
+        self.buffer.front().is_some_and(|front| {
+            front.req.is_reversed == renderer_req.is_reversed
+                && self.frames_in(front).contains(&renderer_req.frame_offset)
+        })
+    }
+
+    /// Replays the first `count` frames of a decoded segment backwards.
+    /// The frames are collected by a separate task so that the buffer manager is not blocked while the segment is decoded.
+    /// If `answer` is given, the first frame played is sent straight to the renderer, and the returned channel holds the rest.
+    fn reverse_segment(
+        &self,
+        mut rx: UnboundedReceiver<PointCloud<PointXyzRgba>>,
+        count: usize,
+        answer: Option<FrameRequest>,
+    ) -> UnboundedReceiver<PointCloud<PointXyzRgba>> {
+        let (reversed_sx, reversed_rx) = tokio::sync::mpsc::unbounded_channel();
+        let buf_out_sx = self.buf_out_sx.clone();
+        tokio::spawn(async move {
+            let mut frames = Vec::with_capacity(count);
+            while frames.len() < count {
+                match rx.recv().await {
+                    Some(pc) => frames.push(pc),
+                    None => break,
+                }
+            }
+            let mut frames = frames.into_iter().rev();
+            if let Some(req) = answer {
+                if let Some(pc) = frames.next() {
+                    _ = buf_out_sx.send((req, pc));
+                }
+            }
+            for pc in frames {
+                _ = reversed_sx.send(pc);
+            }
+        });
+        reversed_rx
+    }
+
     //Send fetch request for the next frame and add it to the buffer
     pub fn prefetch_frame(&mut self, camera_pos: Option<CameraPosition>) {
         assert!(camera_pos.is_some());
@@ -79,7 +177,10 @@ impl BufferManager {
             ..self.buffer.back().unwrap().req
         };
         // The frame prefetched is the next frame of the frame at the back of the buffer
-        let req = self.get_next_frame_req(&last_req);
+        let Some(req) = self.get_next_frame_req(&last_req) else {
+            // playback has ended, there is nothing more to prefetch
+            return;
+        };
         _ = self
             .buf_in_sx
             .send(FetchRequest::new(req, self.buffer.len()));
@@ -95,7 +196,10 @@ impl BufferManager {
         last_req: FrameRequest,
     ) {
         assert!(camera_pos.is_some());
-        let req = self.get_next_frame_req(&last_req);
+        let Some(req) = self.get_next_frame_req(&last_req) else {
+            // playback has ended, there is nothing more to prefetch
+            return;
+        };
         _ = self
             .buf_in_sx
             .send(FetchRequest::new(req, self.buffer.len()));
@@ -163,7 +267,7 @@ impl BufferManager {
 
                             // First, attempt to fulfill the request from the buffer.
                             // Check in cache whether it exists
-                            if !self.buffer.is_empty() && self.buffer.front().unwrap().req.frame_offset == renderer_req.frame_offset {
+                            if self.is_front_requested(&renderer_req) {
                                 let mut front = self.buffer.pop_front().unwrap();
                                 match front.state {
                                     FrameStatus::Fetching | FrameStatus::Decoding => {
@@ -171,7 +275,13 @@ impl BufferManager {
                                         self.frame_to_answer = Some(renderer_req);
                                         self.buffer.push_front(front);
                                     }
-                                    FrameStatus::Ready(remaining_frames, mut rx) => {
+                                    FrameStatus::Ready(mut remaining_frames, mut rx) => {
+                                        // skip the frames the renderer has moved past, e.g. after turning around mid-segment
+                                        while front.req.frame_offset != renderer_req.frame_offset {
+                                            _ = rx.recv().await;
+                                            front.req.frame_offset = Self::next_frame_in_segment(&front.req);
+                                            remaining_frames -= 1;
+                                        }
                                         // send to the renderer
                                         match rx.recv().await {
                                             Some(pc) => {
@@ -185,7 +295,7 @@ impl BufferManager {
                                                 // send to point cloud to renderer
                                                 _ = self.buf_out_sx.send((renderer_req, pc));
                                                 self.frame_to_answer = None;
-                                                front.req.frame_offset += 1;
+                                                front.req.frame_offset = Self::next_frame_in_segment(&front.req);
                                                 front.state = FrameStatus::Ready(remaining_frames - 1, rx);
                                                 //println!("In FrameStatus::Ready, the front is {:?}", front);
                                                 if remaining_frames > 1 {
@@ -208,13 +318,17 @@ impl BufferManager {
                                 }
                             } else {
                                 // It has not been requested, so we send a request to the fetcher to fetch the data
-                                _ = self.buf_in_sx.send(FetchRequest::new(renderer_req, self.buffer.len()));
+                                let fetch_req = FrameRequest {
+                                    frame_offset: self.segment_start(renderer_req.frame_offset),
+                                    ..renderer_req
+                                };
+                                _ = self.buf_in_sx.send(FetchRequest::new(fetch_req, self.buffer.len()));
 
                                 // we update frame_to_answer to indicate that we are waiting to send back this data to renderer.
                                 self.frame_to_answer = Some(renderer_req);
 
                                 // we also update next_fetch_req so that when the fetcher returns the data, we can immediately send the next request to the fetcher
-                                self.buffer.add(renderer_req);
+                                self.buffer.add(fetch_req);
                             }
                         }
                         BufMsg::FetchDone(req) => {
@@ -238,15 +352,36 @@ impl BufferManager {
                             println!("[buffer mgr] received a point cloud result {:?}", &metadata);
                              */
                             let orig_metadata: FrameRequest = metadata.into();
-                            //if this frame is the one that the renderer is awaiting, do not put it back and send it to the renderer
-                            let mut remaining = self.segment_size as usize;
-                            if self.frame_to_answer.is_some()
-                                && metadata.frame_offset
-                                    == self.frame_to_answer.as_ref().unwrap().frame_offset
-                            {
+                            let frames_in_segment = self.frames_in_segment(metadata.frame_offset);
+                            let mut remaining = frames_in_segment as usize;
+                            // the renderer may be awaiting any frame of this segment
+                            let answer = self.frame_to_answer.filter(|req| {
+                                req.is_reversed == metadata.is_reversed
+                                    && (metadata.frame_offset..metadata.frame_offset + frames_in_segment)
+                                        .contains(&req.frame_offset)
+                            });
+                            if metadata.is_reversed {
+                                // frames are decoded from the start of the segment, so playback starts from the awaited frame if any, otherwise from the last frame
+                                let first_frame = answer.map_or(metadata.frame_offset + frames_in_segment - 1, |req| req.frame_offset);
+                                remaining = (first_frame - metadata.frame_offset + 1) as usize;
+                                rx = self.reverse_segment(rx, remaining, answer);
+                                metadata.frame_offset = first_frame;
+                                if answer.is_some() {
+                                    // the awaited frame is sent to the renderer once the segment is reversed
+                                    self.frame_to_answer = None;
+                                    metadata.frame_offset = Self::next_frame_in_segment(&FrameRequest::from(metadata));
+                                    remaining -= 1;
+                                }
+                            } else if let Some(req) = answer {
+                                //if a frame of this segment is the one that the renderer is awaiting, send it to the renderer
+                                while metadata.frame_offset < req.frame_offset {
+                                    _ = rx.recv().await;
+                                    metadata.frame_offset += 1;
+                                    remaining -= 1;
+                                }
                                 let pc = rx.recv().await.unwrap();
                                 // send results to the renderer
-                                _ = self.buf_out_sx.send((self.frame_to_answer.unwrap(), pc));
+                                _ = self.buf_out_sx.send((req, pc));
                                 self.frame_to_answer = None;
                                 metadata.frame_offset += 1;
                                 remaining -= 1;
@@ -262,3 +397,312 @@ impl BufferManager {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::LastValue;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use tokio::sync::mpsc::UnboundedSender;
+
+    const SEGMENT_SIZE: u64 = 10;
+
+    fn new_buffer_manager(total_frames: usize, playback_mode: PlaybackMode) -> BufferManager {
+        let (_to_buf_sx, to_buf_rx) = tokio::sync::mpsc::unbounded_channel();
+        let (buf_in_sx, _buf_in_rx) = tokio::sync::mpsc::unbounded_channel();
+        let (buf_out_sx, _buf_out_rx) = std::sync::mpsc::channel();
+        let (_shutdown_send, shutdown_recv) = tokio::sync::watch::channel(false);
+        BufferManager::new(
+            to_buf_rx,
+            buf_in_sx,
+            buf_out_sx,
+            10,
+            total_frames,
+            (SEGMENT_SIZE, 30),
+            playback_mode,
+            shutdown_recv,
+        )
+    }
+
+    fn frame_offsets(manager: &BufferManager, start: u64, n: usize) -> Vec<Option<(u64, bool)>> {
+        let mut req = FrameRequest {
+            object_id: 0,
+            frame_offset: start,
+            camera_pos: None,
+            is_reversed: false,
+        };
+        (0..n)
+            .map(|_| {
+                let next = manager.get_next_frame_req(&req);
+                if let Some(next) = next {
+                    req = next;
+                }
+                next.map(|r| (r.frame_offset, r.is_reversed))
+            })
+            .collect()
+    }
+
+    /// A point cloud with a single point whose x coordinate is the frame offset
+    fn frame_pc(frame_offset: u64) -> PointCloud<PointXyzRgba> {
+        PointCloud::new(
+            1,
+            vec![PointXyzRgba {
+                x: frame_offset as f32,
+                y: 0.0,
+                z: 0.0,
+                r: 0,
+                g: 0,
+                b: 0,
+                a: 0,
+            }],
+        )
+    }
+
+    /// Simulates the fetcher and the decoder, returning the log of fetched segments
+    fn spawn_fetcher(
+        mut buf_in_rx: UnboundedReceiver<FetchRequest>,
+        to_buf_sx: UnboundedSender<BufMsg>,
+        total_frames: u64,
+    ) -> Arc<Mutex<Vec<(u64, bool)>>> {
+        let fetched = Arc::new(Mutex::new(vec![]));
+        let log = fetched.clone();
+        tokio::spawn(async move {
+            while let Some(req) = buf_in_rx.recv().await {
+                log.lock()
+                    .unwrap()
+                    .push((req.frame_offset, req.is_reversed));
+                _ = to_buf_sx.send(BufMsg::FetchDone(req.into()));
+                let (output_sx, output_rx) = tokio::sync::mpsc::unbounded_channel();
+                _ = to_buf_sx.send(BufMsg::PointCloud((req.into(), output_rx)));
+                for frame in req.frame_offset..(req.frame_offset + SEGMENT_SIZE).min(total_frames) {
+                    _ = output_sx.send(frame_pc(frame));
+                }
+            }
+        });
+        fetched
+    }
+
+    /// Simulates the renderer stepping through the frames, checking that every requested frame is answered.
+    /// Returns the number of frames played.
+    fn render(
+        to_buf_sx: UnboundedSender<BufMsg>,
+        buf_out_rx: std::sync::mpsc::Receiver<(FrameRequest, PointCloud<PointXyzRgba>)>,
+        playback_mode: PlaybackMode,
+        total_frames: u64,
+        max_frames: usize,
+    ) -> usize {
+        let mut position = 0;
+        let mut is_reversed = false;
+        let mut played = 0;
+        loop {
+            _ = to_buf_sx.send(BufMsg::FrameRequest(FrameRequest {
+                object_id: 0,
+                frame_offset: position,
+                camera_pos: None,
+                is_reversed,
+            }));
+            let (req, pc) = buf_out_rx
+                .recv_timeout(Duration::from_secs(5))
+                .expect("requested frame should be answered");
+            assert_eq!(req.frame_offset, position);
+            assert_eq!(pc.points[0].x, position as f32);
+            played += 1;
+            if played == max_frames {
+                return played;
+            }
+            match playback_mode.next_offset(position, total_frames, is_reversed) {
+                Some((next, reversed)) => {
+                    position = next;
+                    is_reversed = reversed;
+                }
+                None => return played,
+            }
+        }
+    }
+
+    /// Plays up to `max_frames` frames through `BufferManager::run`, returning the number of frames played and the fetched segments
+    async fn play(
+        playback_mode: PlaybackMode,
+        total_frames: usize,
+        max_frames: usize,
+    ) -> (usize, Vec<(u64, bool)>) {
+        let (to_buf_sx, to_buf_rx) = tokio::sync::mpsc::unbounded_channel();
+        let (buf_in_sx, buf_in_rx) = tokio::sync::mpsc::unbounded_channel();
+        let (buf_out_sx, buf_out_rx) = std::sync::mpsc::channel();
+        let (shutdown_send, shutdown_recv) = tokio::sync::watch::channel(false);
+        let mut manager = BufferManager::new(
+            to_buf_rx,
+            buf_in_sx,
+            buf_out_sx,
+            4,
+            total_frames,
+            (SEGMENT_SIZE, 30),
+            playback_mode,
+            shutdown_recv,
+        );
+        let fetched = spawn_fetcher(buf_in_rx, to_buf_sx.clone(), total_frames as u64);
+        let handle = tokio::spawn(async move {
+            manager
+                .run(
+                    Box::new(LastValue::new()),
+                    CameraPosition::default(),
+                    None,
+                    None,
+                )
+                .await
+        });
+        let played = tokio::task::spawn_blocking(move || {
+            render(
+                to_buf_sx,
+                buf_out_rx,
+                playback_mode,
+                total_frames as u64,
+                max_frames,
+            )
+        })
+        .await
+        .unwrap();
+        // give the buffer manager time to send any remaining fetch requests
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        _ = shutdown_send.send(true);
+        handle.await.unwrap();
+        let fetched = fetched.lock().unwrap().clone();
+        (played, fetched)
+    }
+
+    #[test]
+    fn test_get_next_frame_req_loop() {
+        let manager = new_buffer_manager(30, PlaybackMode::Loop);
+        assert_eq!(
+            frame_offsets(&manager, 0, 4),
+            vec![
+                Some((10, false)),
+                Some((20, false)),
+                Some((0, false)),
+                Some((10, false))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_get_next_frame_req_ping_pong() {
+        let manager = new_buffer_manager(30, PlaybackMode::PingPong);
+        assert_eq!(
+            frame_offsets(&manager, 0, 7),
+            vec![
+                Some((10, false)),
+                Some((20, false)),
+                Some((20, true)),
+                Some((10, true)),
+                Some((0, true)),
+                Some((0, false)),
+                Some((10, false))
+            ]
+        );
+
+        let manager = new_buffer_manager(10, PlaybackMode::PingPong);
+        assert_eq!(
+            frame_offsets(&manager, 0, 2),
+            vec![Some((0, true)), Some((0, false))]
+        );
+    }
+
+    #[test]
+    fn test_get_next_frame_req_once() {
+        let manager = new_buffer_manager(30, PlaybackMode::Once);
+        assert_eq!(
+            frame_offsets(&manager, 0, 3),
+            vec![Some((10, false)), Some((20, false)), None]
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_run_ping_pong_turnaround() {
+        // forward, backward and forward again through both segments
+        let (played, fetched) = play(PlaybackMode::PingPong, 20, 60).await;
+        assert_eq!(played, 60);
+
+        // every segment is prefetched in the order it is played, with no fetches for missed frames
+        let manager = new_buffer_manager(20, PlaybackMode::PingPong);
+        let mut expected = vec![(0, false)];
+        expected.extend(
+            frame_offsets(&manager, 0, fetched.len() - 1)
+                .into_iter()
+                .flatten(),
+        );
+        assert!(fetched.len() >= 6);
+        assert_eq!(fetched, expected);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_run_reversed_segment_does_not_block() {
+        let (to_buf_sx, to_buf_rx) = tokio::sync::mpsc::unbounded_channel();
+        let (buf_in_sx, mut buf_in_rx) = tokio::sync::mpsc::unbounded_channel();
+        let (buf_out_sx, buf_out_rx) = std::sync::mpsc::channel();
+        let (shutdown_send, shutdown_recv) = tokio::sync::watch::channel(false);
+        let mut manager = BufferManager::new(
+            to_buf_rx,
+            buf_in_sx,
+            buf_out_sx,
+            4,
+            20,
+            (SEGMENT_SIZE, 30),
+            PlaybackMode::PingPong,
+            shutdown_recv,
+        );
+        let handle = tokio::spawn(async move {
+            manager
+                .run(
+                    Box::new(LastValue::new()),
+                    CameraPosition::default(),
+                    None,
+                    None,
+                )
+                .await
+        });
+        let request = |frame_offset| {
+            BufMsg::FrameRequest(FrameRequest {
+                object_id: 0,
+                frame_offset,
+                camera_pos: None,
+                is_reversed: true,
+            })
+        };
+
+        _ = to_buf_sx.send(request(15));
+        let req = buf_in_rx.recv().await.unwrap();
+        assert_eq!((req.frame_offset, req.is_reversed), (10, true));
+        // the decoder keeps the channel open, as it has not finished the segment yet
+        let (output_sx, output_rx) = tokio::sync::mpsc::unbounded_channel();
+        _ = to_buf_sx.send(BufMsg::PointCloud((req.into(), output_rx)));
+        for frame in 10..16 {
+            _ = output_sx.send(frame_pc(frame));
+        }
+
+        let assert_answered = |frame_offset: u64| {
+            let (req, pc) = buf_out_rx
+                .recv_timeout(Duration::from_secs(5))
+                .expect("requested frame should be answered");
+            assert_eq!(req.frame_offset, frame_offset);
+            assert_eq!(pc.points[0].x, frame_offset as f32);
+        };
+        assert_answered(15);
+        _ = to_buf_sx.send(request(14));
+        assert_answered(14);
+
+        _ = shutdown_send.send(true);
+        tokio::time::timeout(Duration::from_secs(5), handle)
+            .await
+            .expect("buffer manager should handle shutdown")
+            .unwrap();
+        drop(output_sx);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_run_once_stops_fetching() {
+        let (played, fetched) = play(PlaybackMode::Once, 20, 100).await;
+        assert_eq!(played, 20);
+        assert_eq!(fetched, vec![(0, false), (10, false)]);
+    }
+}
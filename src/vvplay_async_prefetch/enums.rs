@@ -36,3 +36,5 @@ pub enum ViewportPredictionType {
     /// Last viewport
     Last,
 }
+
+pub use crate::render::wgpu::playback::PlaybackMode;
@@ -17,6 +17,8 @@ pub struct FetchRequest {
     /// The camera position when the frame was requested.
     pub camera_pos: Option<CameraPosition>,
     pub buffer_occupancy: usize,
+    /// Whether the frames are going to be played backwards.
+    pub is_reversed: bool,
 }
 
 impl FetchRequest {
@@ -26,6 +28,7 @@ impl FetchRequest {
             frame_offset: req.frame_offset,
             camera_pos: req.camera_pos,
             buffer_occupancy,
+            is_reversed: req.is_reversed,
         }
     }
 }
@@ -35,6 +38,7 @@ impl From<FetchRequest> for PCMetadata {
         PCMetadata {
             object_id: val.object_id,
             frame_offset: val.frame_offset,
+            is_reversed: val.is_reversed,
         }
     }
 }
@@ -45,6 +49,7 @@ impl From<FetchRequest> for FrameRequest {
             object_id: val.object_id,
             frame_offset: val.frame_offset,
             camera_pos: val.camera_pos,
+            is_reversed: val.is_reversed,
         }
     }
 }